@@ -1,11 +1,20 @@
-use std::time::Instant;
-use std::path::Path;
 use final_project::analyzer::analyze_file;
+use final_project::walker::{walk_files, WalkOptions};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 fn main() {
     let dir = std::env::args().nth(1).expect("usage: bench <dir>");
-    let mut files = Vec::new();
-    collect_files(Path::new(&dir), &mut files);
+
+    let files: Arc<Mutex<Vec<PathBuf>>> = Arc::new(Mutex::new(Vec::new()));
+    walk_files(&[dir], &WalkOptions::default(), || {
+        let files = Arc::clone(&files);
+        move |path: PathBuf| {
+            files.lock().unwrap().push(path);
+        }
+    });
+    let files = Arc::try_unwrap(files).unwrap().into_inner().unwrap();
 
     let start = Instant::now();
     let mut total = 0usize;
@@ -17,13 +26,3 @@ fn main() {
 
     println!("Bench: processed 100 files in {:?}. total_words={}", start.elapsed(), total);
 }
-
-fn collect_files(dir: &Path, out: &mut Vec<std::path::PathBuf>) {
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let p = entry.path();
-            if p.is_dir() { collect_files(&p, out); }
-            else if p.is_file() { out.push(p); }
-        }
-    }
-}
@@ -0,0 +1,69 @@
+/// Process exit codes, mirroring fd's `merge_exitcodes` idea: every outcome
+/// maps to a distinct integer so callers scripting around this CLI can tell
+/// a clean run apart from one where files errored or the user canceled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success,
+    HasErrors,
+    Canceled,
+    GeneralError,
+}
+
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        match self {
+            ExitCode::Success => 0,
+            ExitCode::HasErrors => 1,
+            ExitCode::Canceled => 2,
+            ExitCode::GeneralError => 3,
+        }
+    }
+
+    /// Folds the run's per-file outcomes into a single exit code.
+    /// Cancellation takes priority over ordinary per-file errors so callers
+    /// can tell "user aborted" apart from "some files errored".
+    pub fn merge(had_errors: bool, had_cancellations: bool) -> ExitCode {
+        if had_cancellations {
+            ExitCode::Canceled
+        } else if had_errors {
+            ExitCode::HasErrors
+        } else {
+            ExitCode::Success
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_cancellation_outranks_errors() {
+        assert_eq!(ExitCode::merge(true, true), ExitCode::Canceled);
+    }
+
+    #[test]
+    fn merge_errors_without_cancellation() {
+        assert_eq!(ExitCode::merge(true, false), ExitCode::HasErrors);
+    }
+
+    #[test]
+    fn merge_success_when_clean() {
+        assert_eq!(ExitCode::merge(false, false), ExitCode::Success);
+    }
+
+    #[test]
+    fn codes_are_distinct() {
+        let codes = [
+            ExitCode::Success.code(),
+            ExitCode::HasErrors.code(),
+            ExitCode::Canceled.code(),
+            ExitCode::GeneralError.code(),
+        ];
+        for i in 0..codes.len() {
+            for j in (i + 1)..codes.len() {
+                assert_ne!(codes[i], codes[j]);
+            }
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod analyzer;
+pub mod exit_code;
+pub mod output;
+pub mod progress;
+pub mod status;
+pub mod thread_pool;
+pub mod walker;
@@ -1,72 +1,143 @@
-mod analyzer;
-mod thread_pool;
-
-use analyzer::{analyze_file, FileAnalysis};
-use thread_pool::ThreadPool;
-
+use final_project::analyzer::analyze_file;
+use final_project::exit_code::ExitCode;
+use final_project::output::{spawn_result_receiver, WorkerMessage};
+use final_project::progress::ProgressReporter;
+use final_project::status::Status;
+use final_project::thread_pool::ThreadPool;
+use final_project::walker::{walk_files, WalkOptions};
+
+use ignore::overrides::OverrideBuilder;
+use regex::bytes::Regex;
 use std::collections::HashMap;
 use std::env;
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    mpsc, Arc, Mutex,
 };
 use std::time::Instant;
 use std::{io, thread};
 
-#[derive(Debug, Clone, Copy)]
-enum Status {
-    Queued,
-    Running,
-    Done,
-    Error,
-    Canceled,
+struct Cli {
+    num_threads: usize,
+    dirs: Vec<String>,
+    walk_opts: WalkOptions,
+    globs: Vec<String>,
+    name_regex: Option<Regex>,
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 3 {
-        eprintln!("Usage: {} <num_threads> <dir1> [dir2 dir3 ...]", args[0]);
-        std::process::exit(1);
+fn parse_args(args: &[String]) -> Cli {
+    let mut walk_opts = WalkOptions::default();
+    let mut positional = Vec::new();
+    let mut globs = Vec::new();
+    let mut regex_pat: Option<String> = None;
+
+    let mut iter = args[1..].iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--no-gitignore" => walk_opts.respect_gitignore = false,
+            "--hidden" => walk_opts.hidden = true,
+            "--follow-symlinks" => walk_opts.follow_symlinks = true,
+            "--max-depth" => {
+                let val = iter.next().expect("--max-depth requires a value");
+                walk_opts.max_depth =
+                    Some(val.parse().expect("--max-depth value must be a number"));
+            }
+            "--glob" => {
+                let val = iter.next().expect("--glob requires a pattern");
+                globs.push(val.to_string());
+            }
+            "--regex" => {
+                let val = iter.next().expect("--regex requires a pattern");
+                regex_pat = Some(val.to_string());
+            }
+            other => positional.push(other.to_string()),
+        }
+    }
+
+    if positional.len() < 2 {
+        eprintln!(
+            "Usage: {} [--no-gitignore] [--hidden] [--follow-symlinks] [--max-depth <n>] [--glob <pat>]... [--regex <pat>] <num_threads> <dir1> [dir2 dir3 ...]",
+            args[0]
+        );
+        std::process::exit(ExitCode::GeneralError.code());
+    }
+
+    let num_threads: usize = positional[0].parse().expect("num_threads must be a number");
+    let dirs = positional[1..].to_vec();
+    walk_opts.threads = num_threads.max(1);
+
+    let name_regex = regex_pat.map(|p| Regex::new(&p).expect("invalid --regex pattern"));
+
+    Cli {
+        num_threads,
+        dirs,
+        walk_opts,
+        globs,
+        name_regex,
+    }
+}
+
+/// Builds one `--glob` override matcher per walked root (rather than a
+/// single one anchored to the process's current working directory, or to
+/// just the first root), since anchored patterns like `subdir/keep.txt`
+/// are matched relative to *a* root and the CLI accepts several.
+fn build_overrides(globs: &[String], dirs: &[String]) -> Vec<(PathBuf, ignore::overrides::Override)> {
+    if globs.is_empty() {
+        return Vec::new();
     }
+    dirs.iter()
+        .map(|root| {
+            let mut builder = OverrideBuilder::new(root);
+            for g in globs {
+                builder.add(g).expect("invalid --glob pattern");
+            }
+            let over = builder.build().expect("failed to build glob overrides");
+            (PathBuf::from(root), over)
+        })
+        .collect()
+}
 
-    let num_threads: usize = args[1].parse().expect("num_threads must be a number");
-    let dirs: Vec<String> = args[2..].to_vec();
+/// Checks `path` against whichever walked root's override matcher it falls
+/// under. Files under a root with no matching override entry (or when no
+/// `--glob` was given at all) pass through unfiltered.
+fn passes_overrides(path: &std::path::Path, overrides: &[(PathBuf, ignore::overrides::Override)]) -> bool {
+    if overrides.is_empty() {
+        return true;
+    }
+    match overrides.iter().find(|(root, _)| path.starts_with(root)) {
+        Some((_, over)) => !matches!(over.matched(path, false), ignore::Match::Ignore(_)),
+        None => true,
+    }
+}
 
-    let mut files: Vec<PathBuf> = Vec::new();
-    for d in &dirs {
-        let dir_path = Path::new(d);
-        if dir_path.is_dir() {
-            collect_files(dir_path, &mut files);
-        } else {
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let cli = parse_args(&args);
+
+    for d in &cli.dirs {
+        if !std::path::Path::new(d).is_dir() {
             eprintln!("Warning: {} is not a directory, skipping.", d);
         }
     }
 
-    if files.is_empty() {
-        eprintln!("No files found to process.");
-        return;
-    }
+    let overrides = build_overrides(&cli.globs, &cli.dirs);
 
-    println!("Found {} files to process", files.len());
-    println!("Using {} worker threads", num_threads);
+    println!("Using {} worker threads", cli.num_threads);
     println!("Press Enter to cancel...");
 
-    let results: Arc<Mutex<Vec<FileAnalysis>>> = Arc::new(Mutex::new(Vec::new()));
     let cancel_flag = Arc::new(AtomicBool::new(false));
-
-   
     let status_map: Arc<Mutex<HashMap<String, Status>>> = Arc::new(Mutex::new(HashMap::new()));
+    let total_files = Arc::new(AtomicUsize::new(0));
+    let processed_bytes = Arc::new(AtomicU64::new(0));
 
-    
-    {
-        let mut sm = status_map.lock().unwrap();
-        for p in &files {
-            sm.insert(p.display().to_string(), Status::Queued);
-        }
-    }
+    let (result_tx, result_rx) = mpsc::channel::<WorkerMessage>();
+    let receiver_handle = spawn_result_receiver(
+        result_rx,
+        Arc::clone(&status_map),
+        Arc::clone(&total_files),
+    );
 
-    
     let cancel_for_input = Arc::clone(&cancel_flag);
     thread::spawn(move || {
         let mut buf = String::new();
@@ -75,9 +146,8 @@ fn main() {
         eprintln!("Cancellation requested.");
     });
 
-    let mut pool = ThreadPool::new(num_threads);
+    let mut pool = ThreadPool::new(cli.num_threads);
 
-    
     if let Ok(val) = env::var("RESIZE_TO") {
         if let Ok(n) = val.parse::<usize>() {
             pool.resize(n);
@@ -86,73 +156,105 @@ fn main() {
     }
 
     let start_all = Instant::now();
-    let total_files = files.len();
-
-    
-    for path in files {
-        if cancel_flag.load(Ordering::SeqCst) {
-            let mut sm = status_map.lock().unwrap();
-            for (_k, v) in sm.iter_mut() {
-                if matches!(*v, Status::Queued) {
-                    *v = Status::Canceled;
-                }
-            }
-            break;
-        }
-
-        let results = Arc::clone(&results);
+    let name_regex = cli.name_regex.clone();
+
+    let progress = ProgressReporter::spawn(
+        Arc::clone(&status_map),
+        Arc::clone(&total_files),
+        Arc::clone(&processed_bytes),
+        Arc::clone(&cancel_flag),
+        start_all,
+    );
+
+    walk_files(&cli.dirs, &cli.walk_opts, || {
+        let job_sender = pool.sender();
+        let result_tx = result_tx.clone();
         let cancel_flag = Arc::clone(&cancel_flag);
         let status_map = Arc::clone(&status_map);
-        let full_path = path.display().to_string();
+        let total_files = Arc::clone(&total_files);
+        let processed_bytes = Arc::clone(&processed_bytes);
+        let name_regex = name_regex.clone();
+        let overrides = overrides.clone();
 
-        pool.execute(move || {
+        move |path: PathBuf| {
             if cancel_flag.load(Ordering::SeqCst) {
-                let mut sm = status_map.lock().unwrap();
-                sm.insert(full_path.clone(), Status::Canceled);
                 return;
             }
 
-            {
-                let mut sm = status_map.lock().unwrap();
-                sm.insert(full_path.clone(), Status::Running);
+            if !passes_overrides(&path, &overrides) {
+                return;
             }
 
-            let analysis = analyze_file(&path);
-            let is_error = !analysis.errors.is_empty();
-
-            {
-                let mut r = results.lock().unwrap();
-                r.push(analysis.clone());
+            if let Some(re) = &name_regex {
+                let matches = path
+                    .file_name()
+                    .map(|n| re.is_match(n.to_string_lossy().as_bytes()))
+                    .unwrap_or(false);
+                if !matches {
+                    return;
+                }
             }
 
+            let full_path = path.display().to_string();
+            total_files.fetch_add(1, Ordering::SeqCst);
+
             {
                 let mut sm = status_map.lock().unwrap();
-                sm.insert(full_path.clone(), if is_error { Status::Error } else { Status::Done });
+                sm.insert(full_path.clone(), Status::Queued);
             }
 
-            
-            let done_count = {
-                let sm = status_map.lock().unwrap();
-                sm.values().filter(|s| matches!(s, Status::Done | Status::Error | Status::Canceled)).count()
-            };
-
-            println!(
-                "[{}/{}] {:?} ({}) in {:?}  errors:{}",
-                done_count,
-                total_files,
-                analysis.filename,
-                analysis.full_path,
-                analysis.processing_time,
-                analysis.errors.len()
-            );
-        });
+            let result_tx = result_tx.clone();
+            let cancel_flag = Arc::clone(&cancel_flag);
+            let status_map = Arc::clone(&status_map);
+            let processed_bytes = Arc::clone(&processed_bytes);
+
+            job_sender.execute(move || {
+                if cancel_flag.load(Ordering::SeqCst) {
+                    let mut sm = status_map.lock().unwrap();
+                    sm.insert(full_path.clone(), Status::Canceled);
+                    return;
+                }
+
+                {
+                    let mut sm = status_map.lock().unwrap();
+                    sm.insert(full_path.clone(), Status::Running);
+                }
+
+                let analysis = analyze_file(&path);
+                let is_error = !analysis.errors.is_empty();
+                processed_bytes.fetch_add(analysis.stats.size_bytes, Ordering::SeqCst);
+
+                {
+                    let mut sm = status_map.lock().unwrap();
+                    sm.insert(full_path.clone(), if is_error { Status::Error } else { Status::Done });
+                }
+
+                let msg = if is_error {
+                    WorkerMessage::Failed(analysis)
+                } else {
+                    WorkerMessage::Completed(analysis)
+                };
+                let _ = result_tx.send(msg);
+            });
+        }
+    });
+
+    if total_files.load(Ordering::SeqCst) == 0 && !cancel_flag.load(Ordering::SeqCst) {
+        eprintln!("No files found to process.");
+        std::process::exit(ExitCode::GeneralError.code());
     }
 
-    pool.shutdown();
+    // `walk_files` above has already returned, so every `JobSender` handed
+    // out via `pool.sender()` to a walker thread has been dropped along with
+    // that thread's closure; the pool's own sender is the only one left, so
+    // `shutdown_graceful` can safely drain whatever's still queued instead
+    // of racing `Terminate` messages ahead of in-flight jobs.
+    pool.shutdown_graceful();
+    drop(result_tx);
+    let analyses = receiver_handle.join().expect("result receiver thread panicked");
+    progress.finish(&status_map, &total_files, &processed_bytes, start_all);
     let total_time = start_all.elapsed();
 
-    
-    let analyses = results.lock().unwrap();
     let sm = status_map.lock().unwrap();
 
     let mut total_words = 0usize;
@@ -191,22 +293,8 @@ fn main() {
             }
         }
     }
-}
 
-fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
-    match std::fs::read_dir(dir) {
-        Ok(entries) => {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    collect_files(&path, out);
-                } else if path.is_file() {
-                    out.push(path);
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("read_dir error on {}: {}", dir.display(), e);
-        }
-    }
+    let had_cancellations = canceled > 0 || cancel_flag.load(Ordering::SeqCst);
+    let exit_code = ExitCode::merge(total_errors > 0, had_cancellations);
+    std::process::exit(exit_code.code());
 }
@@ -0,0 +1,174 @@
+use crate::analyzer::FileAnalysis;
+use crate::status::Status;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How large the buffer is allowed to grow before it's flushed and printing
+/// switches to streaming mode.
+const BUFFER_CAP: usize = 1000;
+/// How long to stay in buffering mode before flushing, even if the buffer
+/// hasn't filled up.
+const BUFFER_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// A single file's outcome, sent from a worker job to the result receiver.
+pub enum WorkerMessage {
+    Completed(FileAnalysis),
+    Failed(FileAnalysis),
+}
+
+impl WorkerMessage {
+    fn into_analysis(self) -> FileAnalysis {
+        match self {
+            WorkerMessage::Completed(a) | WorkerMessage::Failed(a) => a,
+        }
+    }
+}
+
+enum Mode {
+    Buffering,
+    Streaming,
+}
+
+/// Spawns the dedicated receiver thread that turns worker results into
+/// output.
+///
+/// Results start out buffered: they're collected into a `Vec` until either
+/// `BUFFER_CAP` entries have piled up or `BUFFER_TIMEOUT` has elapsed since
+/// the run started, whichever comes first. At that point the buffer is
+/// sorted by path and flushed, and the receiver flips to streaming mode,
+/// where every subsequent result is printed as soon as it arrives. This
+/// means fast scans print a single clean, sorted batch, while long scans
+/// stay responsive with live per-file output - and ordering no longer
+/// depends on thread scheduling.
+pub fn spawn_result_receiver(
+    rx: Receiver<WorkerMessage>,
+    status_map: Arc<Mutex<HashMap<String, Status>>>,
+    total_files: Arc<AtomicUsize>,
+) -> thread::JoinHandle<Vec<FileAnalysis>> {
+    thread::spawn(move || {
+        let deadline = Instant::now() + BUFFER_TIMEOUT;
+        let mut mode = Mode::Buffering;
+        let mut buffer: Vec<FileAnalysis> = Vec::new();
+        let mut all: Vec<FileAnalysis> = Vec::new();
+
+        loop {
+            match mode {
+                Mode::Buffering => {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    match rx.recv_timeout(remaining) {
+                        Ok(msg) => {
+                            buffer.push(msg.into_analysis());
+                            if buffer.len() >= BUFFER_CAP || Instant::now() >= deadline {
+                                flush(&mut buffer, &mut all, &status_map, &total_files);
+                                mode = Mode::Streaming;
+                            }
+                        }
+                        Err(RecvTimeoutError::Timeout) => {
+                            flush(&mut buffer, &mut all, &status_map, &total_files);
+                            mode = Mode::Streaming;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => {
+                            flush(&mut buffer, &mut all, &status_map, &total_files);
+                            break;
+                        }
+                    }
+                }
+                Mode::Streaming => match rx.recv() {
+                    Ok(msg) => {
+                        let analysis = msg.into_analysis();
+                        print_line(&analysis, &status_map, &total_files);
+                        all.push(analysis);
+                    }
+                    Err(_) => break,
+                },
+            }
+        }
+
+        all
+    })
+}
+
+fn flush(
+    buffer: &mut Vec<FileAnalysis>,
+    all: &mut Vec<FileAnalysis>,
+    status_map: &Arc<Mutex<HashMap<String, Status>>>,
+    total_files: &Arc<AtomicUsize>,
+) {
+    buffer.sort_by(|a, b| a.full_path.cmp(&b.full_path));
+    for analysis in buffer.drain(..) {
+        print_line(&analysis, status_map, total_files);
+        all.push(analysis);
+    }
+}
+
+fn print_line(
+    analysis: &FileAnalysis,
+    status_map: &Arc<Mutex<HashMap<String, Status>>>,
+    total_files: &Arc<AtomicUsize>,
+) {
+    let done_count = {
+        let sm = status_map.lock().unwrap();
+        sm.values()
+            .filter(|s| matches!(s, Status::Done | Status::Error | Status::Canceled))
+            .count()
+    };
+
+    println!(
+        "[{}/{}] {:?} ({}) in {:?}  errors:{}",
+        done_count,
+        total_files.load(Ordering::SeqCst),
+        analysis.filename,
+        analysis.full_path,
+        analysis.processing_time,
+        analysis.errors.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::FileStats;
+
+    fn analysis(path: &str) -> FileAnalysis {
+        FileAnalysis {
+            filename: path.to_string(),
+            full_path: path.to_string(),
+            stats: FileStats::default(),
+            errors: Vec::new(),
+            processing_time: Duration::default(),
+        }
+    }
+
+    #[test]
+    fn flush_sorts_buffer_by_path_and_moves_it_into_all() {
+        let status_map = Arc::new(Mutex::new(HashMap::new()));
+        let total_files = Arc::new(AtomicUsize::new(3));
+
+        let mut buffer = vec![analysis("c.txt"), analysis("a.txt"), analysis("b.txt")];
+        let mut all = Vec::new();
+
+        flush(&mut buffer, &mut all, &status_map, &total_files);
+
+        assert!(buffer.is_empty());
+        let paths: Vec<_> = all.iter().map(|a| a.full_path.as_str()).collect();
+        assert_eq!(paths, ["a.txt", "b.txt", "c.txt"]);
+    }
+
+    #[test]
+    fn flush_of_empty_buffer_leaves_all_untouched() {
+        let status_map = Arc::new(Mutex::new(HashMap::new()));
+        let total_files = Arc::new(AtomicUsize::new(0));
+
+        let mut buffer = Vec::new();
+        let mut all = vec![analysis("existing.txt")];
+
+        flush(&mut buffer, &mut all, &status_map, &total_files);
+
+        assert_eq!(all.len(), 1);
+    }
+}
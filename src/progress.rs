@@ -0,0 +1,149 @@
+use crate::status::Status;
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const REPORT_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A background progress-reporter thread, inspired by rust-analyzer's
+/// work-done progress begin/report/end lifecycle: it wakes on a fixed
+/// interval and prints one updating line with percent complete, throughput,
+/// and an ETA, so long scans give continuous feedback instead of silence
+/// between per-file completions.
+pub struct ProgressReporter {
+    stop: Arc<AtomicBool>,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl ProgressReporter {
+    pub fn spawn(
+        status_map: Arc<Mutex<HashMap<String, Status>>>,
+        total_files: Arc<AtomicUsize>,
+        processed_bytes: Arc<AtomicU64>,
+        cancel_flag: Arc<AtomicBool>,
+        start: Instant,
+    ) -> ProgressReporter {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_for_thread = Arc::clone(&stop);
+
+        eprintln!("progress[begin]: scanning...");
+
+        let handle = thread::spawn(move || {
+            while !stop_for_thread.load(Ordering::SeqCst) && !cancel_flag.load(Ordering::SeqCst) {
+                thread::sleep(REPORT_INTERVAL);
+                if stop_for_thread.load(Ordering::SeqCst) {
+                    break;
+                }
+                report("report", &status_map, &total_files, &processed_bytes, start);
+            }
+        });
+
+        ProgressReporter {
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Stops the reporter thread and prints the final "end" summary line.
+    pub fn finish(
+        mut self,
+        status_map: &Arc<Mutex<HashMap<String, Status>>>,
+        total_files: &Arc<AtomicUsize>,
+        processed_bytes: &Arc<AtomicU64>,
+        start: Instant,
+    ) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(h) = self.handle.take() {
+            let _ = h.join();
+        }
+        report("end", status_map, total_files, processed_bytes, start);
+    }
+}
+
+fn report(
+    phase: &str,
+    status_map: &Arc<Mutex<HashMap<String, Status>>>,
+    total_files: &Arc<AtomicUsize>,
+    processed_bytes: &Arc<AtomicU64>,
+    start: Instant,
+) {
+    let total = total_files.load(Ordering::SeqCst);
+    let done = {
+        let sm = status_map.lock().unwrap();
+        sm.values()
+            .filter(|s| matches!(s, Status::Done | Status::Error | Status::Canceled))
+            .count()
+    };
+    let bytes = processed_bytes.load(Ordering::SeqCst);
+    let elapsed = start.elapsed().as_secs_f64().max(0.001);
+
+    let (percent, files_per_sec, bytes_per_sec, eta) = throughput_stats(total, done, bytes, elapsed);
+
+    let line = format!(
+        "progress[{phase}]: {done}/{total} ({percent:.1}%)  {files_per_sec:.1} files/s  {bytes_per_sec:.0} B/s  ETA {eta}    "
+    );
+    // Interval reports rewrite the same line with a carriage return; the
+    // final "end" report terminates it with a newline.
+    if phase == "end" {
+        eprintln!("\r{line}");
+    } else {
+        eprint!("\r{line}");
+    }
+}
+
+/// Pure throughput/ETA math behind [`report`]'s status line, split out so it
+/// can be exercised without a real clock: percent complete, files/sec,
+/// bytes/sec, and a formatted ETA (or `"-"` when it can't be estimated yet).
+fn throughput_stats(total: usize, done: usize, bytes: u64, elapsed: f64) -> (f64, f64, f64, String) {
+    let percent = if total == 0 {
+        0.0
+    } else {
+        (done as f64 / total as f64) * 100.0
+    };
+    let files_per_sec = done as f64 / elapsed;
+    let bytes_per_sec = bytes as f64 / elapsed;
+
+    let eta = if files_per_sec > 0.0 && total > done {
+        format!("{:.1}s", (total - done) as f64 / files_per_sec)
+    } else {
+        "-".to_string()
+    };
+
+    (percent, files_per_sec, bytes_per_sec, eta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn throughput_stats_computes_percent_and_rates() {
+        let (percent, files_per_sec, bytes_per_sec, eta) = throughput_stats(200, 50, 1000, 10.0);
+        assert_eq!(percent, 25.0);
+        assert_eq!(files_per_sec, 5.0);
+        assert_eq!(bytes_per_sec, 100.0);
+        assert_eq!(eta, "30.0s");
+    }
+
+    #[test]
+    fn throughput_stats_eta_is_dash_before_progress_is_made() {
+        let (_, _, _, eta) = throughput_stats(200, 0, 0, 10.0);
+        assert_eq!(eta, "-");
+    }
+
+    #[test]
+    fn throughput_stats_eta_is_dash_once_done() {
+        let (percent, _, _, eta) = throughput_stats(10, 10, 500, 5.0);
+        assert_eq!(percent, 100.0);
+        assert_eq!(eta, "-");
+    }
+
+    #[test]
+    fn throughput_stats_percent_is_zero_with_no_files() {
+        let (percent, _, _, _) = throughput_stats(0, 0, 0, 1.0);
+        assert_eq!(percent, 0.0);
+    }
+}
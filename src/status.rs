@@ -0,0 +1,11 @@
+/// Per-file lifecycle state, tracked in a shared status map so other
+/// subsystems (buffered output, progress reporting) can read live counts
+/// without threading per-file state through every call site.
+#[derive(Debug, Clone, Copy)]
+pub enum Status {
+    Queued,
+    Running,
+    Done,
+    Error,
+    Canceled,
+}
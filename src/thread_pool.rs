@@ -1,4 +1,4 @@
-use std::sync::{mpsc, Arc, Mutex};
+use crossbeam_channel::{bounded, Receiver, Sender};
 use std::thread;
 
 type Job = Box<dyn FnOnce() + Send + 'static>;
@@ -10,30 +10,46 @@ enum Message {
 
 pub struct ThreadPool {
     workers: Vec<Worker>,
-    sender: mpsc::Sender<Message>,
-    receiver: Arc<Mutex<mpsc::Receiver<Message>>>,
+    sender: Option<Sender<Message>>,
+    receiver: Receiver<Message>,
 }
 
 impl ThreadPool {
     pub fn new(size: usize) -> ThreadPool {
         assert!(size > 0);
 
-        let (sender, receiver) = mpsc::channel::<Message>();
-        let receiver = Arc::new(Mutex::new(receiver));
+        // Bounded so a producer enqueuing faster than the pool can drain
+        // (e.g. a parallel directory walker) blocks in `execute` instead of
+        // buffering every pending `Job` in memory. Capacity scales with
+        // worker count to absorb a reasonable burst without stalling.
+        let (sender, receiver) = bounded::<Message>(size * 4);
 
         let mut workers = Vec::with_capacity(size);
         for id in 0..size {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+            workers.push(Worker::new(id, receiver.clone()));
         }
 
-        ThreadPool { workers, sender, receiver }
+        ThreadPool {
+            workers,
+            sender: Some(sender),
+            receiver,
+        }
     }
 
     pub fn execute<F>(&self, f: F)
     where
         F: FnOnce() + Send + 'static,
     {
-        let _ = self.sender.send(Message::NewJob(Box::new(f)));
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(Message::NewJob(Box::new(f)));
+        }
+    }
+
+    /// Returns a cloneable handle that can submit jobs from any thread,
+    /// for producers (like a parallel directory walker) that feed the pool
+    /// from outside the thread that owns it.
+    pub fn sender(&self) -> JobSender {
+        JobSender(self.sender.clone().expect("thread pool already shut down"))
     }
 
     pub fn resize(&mut self, new_size: usize) {
@@ -41,30 +57,53 @@ impl ThreadPool {
         let current = self.workers.len();
 
         if new_size > current {
-           
             for id in current..new_size {
-                self.workers.push(Worker::new(id, Arc::clone(&self.receiver)));
+                self.workers.push(Worker::new(id, self.receiver.clone()));
             }
         } else if new_size < current {
-            
+            // Signal and join one popped worker at a time via its own
+            // dedicated stop channel, rather than pushing `to_remove`
+            // `Terminate`s into the shared job queue: with a shared
+            // receiver, any worker (not just the ones being removed) can
+            // pick up a `Terminate`, leaving a popped worker blocked
+            // forever waiting for one that never arrives.
             let to_remove = current - new_size;
-
-            for _ in 0..to_remove {
-                let _ = self.sender.send(Message::Terminate);
-            }
-
             for _ in 0..to_remove {
                 if let Some(mut w) = self.workers.pop() {
+                    w.stop();
                     w.join();
                 }
             }
         }
     }
 
+    /// Terminates workers as soon as possible: pushes one `Terminate`
+    /// message per worker and joins. Because `Terminate` messages share the
+    /// same queue as `NewJob` messages, a `Terminate` can race ahead of a
+    /// `NewJob` sent concurrently by another producer (e.g. a walker thread
+    /// still feeding the pool), in which case that job is never run.
     pub fn shutdown(&mut self) {
-        for _ in &self.workers {
-            let _ = self.sender.send(Message::Terminate);
+        if let Some(sender) = &self.sender {
+            for _ in &self.workers {
+                let _ = sender.send(Message::Terminate);
+            }
+        }
+        while let Some(mut w) = self.workers.pop() {
+            w.join();
         }
+    }
+
+    /// Stops accepting new jobs and lets the queue fully drain before
+    /// joining workers, so no job already in flight or already queued is
+    /// dropped the way it can be with [`ThreadPool::shutdown`].
+    ///
+    /// This is done by dropping the pool's own sender (and any clones
+    /// handed out via [`ThreadPool::sender`] must also be dropped by the
+    /// caller): once every sender is gone, workers keep pulling queued
+    /// `NewJob` messages until the channel is empty, and only then see it
+    /// disconnect and exit.
+    pub fn shutdown_graceful(&mut self) {
+        self.sender = None;
         while let Some(mut w) = self.workers.pop() {
             w.join();
         }
@@ -77,25 +116,51 @@ impl Drop for ThreadPool {
     }
 }
 
+/// A cloneable, `Send + Sync` handle for submitting jobs to a [`ThreadPool`]
+/// from producer threads that don't own the pool itself.
+#[derive(Clone)]
+pub struct JobSender(Sender<Message>);
+
+impl JobSender {
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let _ = self.0.send(Message::NewJob(Box::new(f)));
+    }
+}
+
 struct Worker {
     handle: Option<thread::JoinHandle<()>>,
+    stop_tx: Sender<()>,
 }
 
 impl Worker {
-    fn new(_id: usize, receiver: Arc<Mutex<mpsc::Receiver<Message>>>) -> Worker {
+    fn new(_id: usize, receiver: Receiver<Message>) -> Worker {
+        let (stop_tx, stop_rx) = bounded::<()>(1);
+
         let handle = thread::spawn(move || loop {
-            let message = {
-                let rx = receiver.lock().unwrap();
-                rx.recv()
-            };
-
-            match message {
-                Ok(Message::NewJob(job)) => job(),
-                Ok(Message::Terminate) | Err(_) => break,
+            crossbeam_channel::select! {
+                recv(stop_rx) -> _ => break,
+                recv(receiver) -> msg => match msg {
+                    Ok(Message::NewJob(job)) => job(),
+                    Ok(Message::Terminate) | Err(_) => break,
+                },
             }
         });
 
-        Worker { handle: Some(handle) }
+        Worker {
+            handle: Some(handle),
+            stop_tx,
+        }
+    }
+
+    /// Tells this specific worker to stop, independent of the shared job
+    /// queue, so callers can target exactly this worker (e.g. when shrinking
+    /// the pool) without relying on whichever worker happens to dequeue a
+    /// `Terminate` next.
+    fn stop(&self) {
+        let _ = self.stop_tx.send(());
     }
 
     fn join(&mut self) {
@@ -135,4 +200,21 @@ mod tests {
         pool.resize(3);
         assert_eq!(pool.workers.len(), 3);
     }
+
+    #[test]
+    fn pool_shutdown_graceful_drains_queue() {
+        let mut pool = ThreadPool::new(2);
+        let counter = Arc::new(Mutex::new(0usize));
+
+        for _ in 0..20 {
+            let counter = Arc::clone(&counter);
+            pool.execute(move || {
+                let mut c = counter.lock().unwrap();
+                *c += 1;
+            });
+        }
+
+        pool.shutdown_graceful();
+        assert_eq!(*counter.lock().unwrap(), 20);
+    }
 }
@@ -0,0 +1,80 @@
+use ignore::{DirEntry, WalkBuilder, WalkState};
+use std::path::PathBuf;
+
+/// Controls how [`walk_files`] traverses the given roots.
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    /// Honor `.gitignore` / `.ignore` files found while walking.
+    pub respect_gitignore: bool,
+    /// Include hidden files and directories (dotfiles).
+    pub hidden: bool,
+    /// Follow symlinks instead of skipping them.
+    pub follow_symlinks: bool,
+    /// Maximum descent depth from each root, if any.
+    pub max_depth: Option<usize>,
+    /// Number of threads the walker itself should use.
+    pub threads: usize,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        WalkOptions {
+            respect_gitignore: true,
+            hidden: false,
+            follow_symlinks: false,
+            max_depth: None,
+            threads: 1,
+        }
+    }
+}
+
+/// Walks `roots` according to `opts`, feeding every regular file discovered
+/// to a per-thread visitor built by `mk_on_file`.
+///
+/// The traversal runs on `opts.threads` worker threads via the `ignore`
+/// crate's parallel walker, so entries are handed off as soon as they're
+/// found rather than being collected into a `Vec` first. `mk_on_file` is
+/// called once per walker thread (mirroring `ignore`'s own visitor-factory
+/// pattern) so each thread gets its own independent `FnMut`, e.g. one
+/// holding its own cloned channel sender.
+///
+/// `--glob` override filtering is applied by the caller inside `mk_on_file`
+/// rather than here, since an override's anchored patterns are relative to
+/// a single root and `roots` may hold more than one.
+pub fn walk_files<M, F>(roots: &[String], opts: &WalkOptions, mut mk_on_file: M)
+where
+    M: FnMut() -> F,
+    F: FnMut(PathBuf) + Send + 'static,
+{
+    let mut roots = roots.iter();
+    let first = match roots.next() {
+        Some(r) => r,
+        None => return,
+    };
+
+    let mut builder = WalkBuilder::new(first);
+    for root in roots {
+        builder.add(root);
+    }
+
+    builder
+        .git_ignore(opts.respect_gitignore)
+        .git_exclude(opts.respect_gitignore)
+        .ignore(opts.respect_gitignore)
+        .hidden(!opts.hidden)
+        .follow_links(opts.follow_symlinks)
+        .max_depth(opts.max_depth)
+        .threads(opts.threads.max(1));
+
+    builder.build_parallel().run(|| {
+        let mut on_file = mk_on_file();
+        Box::new(move |result: Result<DirEntry, ignore::Error>| {
+            if let Ok(entry) = result {
+                if entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                    on_file(entry.into_path());
+                }
+            }
+            WalkState::Continue
+        })
+    });
+}
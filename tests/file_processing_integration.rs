@@ -21,7 +21,7 @@ fn integration_analyze_real_file() {
 
 #[test]
 fn error_handling_missing_file() {
-    let mut path = PathBuf::from(std::env::temp_dir());
+    let mut path: PathBuf = std::env::temp_dir();
     path.push("this_file_should_not_exist_12345.txt");
 
     let result = analyze_file(&path);